@@ -81,14 +81,378 @@ fn handle_result<T>(result: Result<T, icechunk::store::StoreError>) -> Result<T,
     result.map_err(handle_err)
 }
 
+/// A single item in conflict between this session's pending changes and commits already made to
+/// the branch tip, as surfaced to a [`ConflictResolver`].
+#[derive(Debug, Clone)]
+pub struct CommitConflict {
+    /// The zarrs [`StoreKey`] touched by both this session and an intervening commit.
+    pub key: StoreKey,
+}
+
+/// A decision for a single [`CommitConflict`], returned by a [`ConflictResolver`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictDecision {
+    /// Keep this session's pending change, discarding the conflicting change on the branch tip.
+    KeepOurs,
+    /// Discard this session's pending change in favour of the change already on the branch tip.
+    KeepTheirs,
+    /// Abort the commit entirely, surfacing the conflict to the caller.
+    Abort,
+}
+
+/// A user-supplied strategy for resolving conflicts encountered during
+/// [`AsyncIcechunkStore::commit_rebasing`].
+///
+/// Implementations are invoked once per [`CommitConflict`] between this session's pending change
+/// set and the commits made to the branch tip since the session's base snapshot.
+pub trait ConflictResolver {
+    /// Decide how to resolve a single conflict.
+    fn resolve(&self, conflict: &CommitConflict) -> ConflictDecision;
+}
+
+/// An error produced while attempting a conflict-aware [`AsyncIcechunkStore::commit_rebasing`].
+#[derive(Debug, thiserror::Error)]
+pub enum CommitRebasingError {
+    /// A [`ConflictResolver`] chose to abort the commit.
+    #[error("commit aborted due to an unresolved conflict on {0}")]
+    Aborted(StoreKey),
+    /// The initial commit attempt and all `max_retries` retries failed.
+    #[error("exceeded {0} retries while rebasing the commit")]
+    RetriesExceeded(usize),
+    /// An underlying icechunk operation failed.
+    #[error(transparent)]
+    Icechunk(#[from] icechunk::session::SessionError),
+    /// A node path or chunk index in a transaction log could not be translated into a zarrs
+    /// [`StoreKey`].
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+}
+
+impl AsyncIcechunkStore {
+    /// Commit the session's pending changes, automatically rebasing onto the current branch tip
+    /// and retrying on conflicts.
+    ///
+    /// If the branch has advanced since this session's base snapshot, the transaction logs of the
+    /// intervening commits are compared against the keys touched by this session, translating
+    /// node/chunk changes into zarrs [`StoreKey`]s the same way as [`AsyncIcechunkStore::diff`].
+    /// Disjoint writes are fast-forwarded onto the new tip and the commit is retried; overlapping
+    /// writes are resolved one key at a time by `resolver`, whose [`ConflictDecision::Abort`]
+    /// immediately fails the commit. At most `max_retries` rebase-and-retry rounds are attempted
+    /// before giving up with [`CommitRebasingError::RetriesExceeded`].
+    ///
+    /// This resolves conflicts at the granularity of zarrs [`StoreKey`]s rather than going through
+    /// `commit`'s own conflict-resolution hook, so [`ConflictResolver`] is this crate's own trait
+    /// and not an adapter over one icechunk ships. Treat this function as the
+    /// supported entry point for conflict-aware commits rather than reaching for the
+    /// session/transaction-log methods it calls directly.
+    pub async fn commit_rebasing<R: ConflictResolver>(
+        &self,
+        message: &str,
+        resolver: &R,
+        max_retries: usize,
+    ) -> Result<icechunk::format::snapshot::SnapshotId, CommitRebasingError> {
+        for _attempt in 0..=max_retries {
+            let mut session = self.icechunk_session.write().await;
+            match session.commit(message, None).await {
+                Ok(snapshot_id) => return Ok(snapshot_id),
+                Err(err) if !matches!(err.kind(), icechunk::session::SessionErrorKind::Conflict { .. }) =>
+                {
+                    return Err(CommitRebasingError::Icechunk(err));
+                }
+                Err(_) => {}
+            }
+
+            // The branch tip has advanced since `session`'s base snapshot. Walk the intervening
+            // transaction logs and compare the keys they touched against the keys touched by this
+            // session's own pending changes. Each log enumerates created/modified/deleted nodes
+            // (zarr metadata) and chunks separately, translated into zarrs keys the same way as
+            // `AsyncIcechunkStore::diff`.
+            let base_snapshot = session.snapshot_id().clone();
+            let branch_tip = session.branch_tip_snapshot_id().await?;
+            let mut changed_by_others = std::collections::HashSet::new();
+            for log in session.transaction_logs_between(&base_snapshot, &branch_tip).await? {
+                for change in log.added_nodes() {
+                    changed_by_others.insert(metadata_store_key(change.path())?);
+                }
+                for change in log.modified_nodes() {
+                    changed_by_others.insert(metadata_store_key(change.path())?);
+                }
+                for change in log.deleted_nodes() {
+                    changed_by_others.insert(metadata_store_key(change.path())?);
+                }
+                for change in log.added_chunks() {
+                    changed_by_others.insert(chunk_store_key(change.path(), change.chunk_indices())?);
+                }
+                for change in log.modified_chunks() {
+                    changed_by_others.insert(chunk_store_key(change.path(), change.chunk_indices())?);
+                }
+                for change in log.deleted_chunks() {
+                    changed_by_others.insert(chunk_store_key(change.path(), change.chunk_indices())?);
+                }
+            }
+
+            let touched_by_us = session.pending_changes().touched_keys();
+            let conflicting: Vec<_> = touched_by_us
+                .iter()
+                .filter(|key| changed_by_others.contains(*key))
+                .cloned()
+                .collect();
+
+            if conflicting.is_empty() {
+                // Disjoint writes: fast-forward and retry the commit on the next iteration. The
+                // rebase pulls in another writer's committed data, so the cached size/quota
+                // accounting (which only tracks this store's own writes) is now stale.
+                session.rebase_onto(&branch_tip).await?;
+                drop(session);
+                self.invalidate_sizes().await;
+                continue;
+            }
+
+            for key in &conflicting {
+                match resolver.resolve(&CommitConflict { key: key.clone() }) {
+                    ConflictDecision::KeepOurs => {
+                        // Our pending write for `key` is kept as-is; the rebase below simply
+                        // moves the session's base forward without touching it.
+                    }
+                    ConflictDecision::KeepTheirs => {
+                        session.discard_pending_change(key).await?;
+                    }
+                    ConflictDecision::Abort => {
+                        return Err(CommitRebasingError::Aborted(key.clone()));
+                    }
+                }
+            }
+            session.rebase_onto(&branch_tip).await?;
+            drop(session);
+            self.invalidate_sizes().await;
+        }
+        Err(CommitRebasingError::RetriesExceeded(max_retries))
+    }
+}
+
+/// The zarrs [`StoreKey`]s that differ between two snapshots, as returned by
+/// [`AsyncIcechunkStore::diff`].
+#[derive(Debug, Clone, Default)]
+pub struct StoreKeysDiff {
+    /// Keys present in `to` but not in `from`.
+    pub added: StoreKeys,
+    /// Keys present in both snapshots with different contents.
+    pub modified: StoreKeys,
+    /// Keys present in `from` but not in `to`.
+    pub deleted: StoreKeys,
+}
+
+/// Strip the leading `/` icechunk uses for an absolute node path, e.g. `/array` -> `array` and
+/// the root path `/` -> `""`.
+fn node_path_prefix(node_path: &str) -> &str {
+    node_path.trim_start_matches('/')
+}
+
+/// Format an icechunk chunk index as the zarrs default chunk key under `node_path`, e.g.
+/// `path/c/0/0`.
+fn chunk_store_key(node_path: &str, chunk_indices: &[u64]) -> Result<StoreKey, StorageError> {
+    let prefix = node_path_prefix(node_path);
+    let mut key = if prefix.is_empty() {
+        "c".to_string()
+    } else {
+        format!("{prefix}/c")
+    };
+    for index in chunk_indices {
+        key.push('/');
+        key.push_str(&index.to_string());
+    }
+    StoreKey::new(key)
+}
+
+/// Format an icechunk node path as its zarrs metadata key, e.g. `path/zarr.json`, or `zarr.json`
+/// (no leading slash) for the root node path `/`.
+fn metadata_store_key(node_path: &str) -> Result<StoreKey, StorageError> {
+    let prefix = node_path_prefix(node_path);
+    StoreKey::new(if prefix.is_empty() {
+        "zarr.json".to_string()
+    } else {
+        format!("{prefix}/zarr.json")
+    })
+}
+
+/// The net effect of a transaction log's events on a single key, folded in commit order by
+/// [`fold_change`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChangeKind {
+    Added,
+    Modified,
+    Deleted,
+}
+
+/// Fold one more add/modify/delete event for `key` into its running net classification in `net`.
+///
+/// A key added and later deleted within the range existed in neither `from` nor `to`, so it is
+/// dropped entirely rather than left classified as an addition. A key deleted and later recreated
+/// within the range did exist in `from`, so the net effect is a modification, not an addition.
+fn fold_change(net: &mut std::collections::HashMap<StoreKey, ChangeKind>, key: StoreKey, kind: ChangeKind) {
+    match (net.get(&key).copied(), kind) {
+        (Some(ChangeKind::Added), ChangeKind::Deleted) => {
+            net.remove(&key);
+        }
+        (Some(ChangeKind::Deleted), ChangeKind::Added) => {
+            net.insert(key, ChangeKind::Modified);
+        }
+        (Some(ChangeKind::Added), ChangeKind::Modified) => {
+            // Still a net addition; touching a freshly-created key again doesn't change that.
+        }
+        (_, kind) => {
+            net.insert(key, kind);
+        }
+    }
+}
+
+impl AsyncIcechunkStore {
+    /// Report the zarrs [`StoreKey`]s that changed between two snapshots.
+    ///
+    /// The snapshot ancestry is walked from `to` back to the common ancestor with `from`, and the
+    /// modified-node and modified-chunk records of every intervening transaction log are folded,
+    /// in commit order, into a net add/modify/delete classification per key and translated into
+    /// zarrs keys (`path/zarr.json` for metadata, `path/c/0/0` for chunks under the default chunk
+    /// key encoding). This lets a consumer drive a minimal incremental sync against
+    /// [`AsyncReadableStorageTraits::get`]/[`AsyncWritableStorageTraits::erase`] instead of
+    /// re-listing the whole store.
+    pub async fn diff(
+        &self,
+        from: &icechunk::repository::VersionInfo,
+        to: &icechunk::repository::VersionInfo,
+    ) -> Result<StoreKeysDiff, StorageError> {
+        let session = self.icechunk_session.read().await;
+        let from_snapshot = session.resolve_version(from).await.map_err(handle_err)?;
+        let to_snapshot = session.resolve_version(to).await.map_err(handle_err)?;
+
+        let mut net = std::collections::HashMap::new();
+
+        let logs = session
+            .transaction_logs_between(&from_snapshot, &to_snapshot)
+            .await
+            .map_err(handle_err)?;
+        for log in logs {
+            for change in log.added_nodes() {
+                fold_change(&mut net, metadata_store_key(change.path())?, ChangeKind::Added);
+            }
+            for change in log.modified_nodes() {
+                fold_change(&mut net, metadata_store_key(change.path())?, ChangeKind::Modified);
+            }
+            for change in log.deleted_nodes() {
+                fold_change(&mut net, metadata_store_key(change.path())?, ChangeKind::Deleted);
+            }
+            for change in log.added_chunks() {
+                fold_change(
+                    &mut net,
+                    chunk_store_key(change.path(), change.chunk_indices())?,
+                    ChangeKind::Added,
+                );
+            }
+            for change in log.modified_chunks() {
+                fold_change(
+                    &mut net,
+                    chunk_store_key(change.path(), change.chunk_indices())?,
+                    ChangeKind::Modified,
+                );
+            }
+            for change in log.deleted_chunks() {
+                fold_change(
+                    &mut net,
+                    chunk_store_key(change.path(), change.chunk_indices())?,
+                    ChangeKind::Deleted,
+                );
+            }
+        }
+
+        let mut added = vec![];
+        let mut modified = vec![];
+        let mut deleted = vec![];
+        for (key, kind) in net {
+            match kind {
+                ChangeKind::Added => added.push(key),
+                ChangeKind::Modified => modified.push(key),
+                ChangeKind::Deleted => deleted.push(key),
+            }
+        }
+
+        Ok(StoreKeysDiff {
+            added: added.into_iter().collect(),
+            modified: modified.into_iter().collect(),
+            deleted: deleted.into_iter().collect(),
+        })
+    }
+}
+
+/// A cap on the total number of bytes an [`AsyncIcechunkStore`] is allowed to hold, configured at
+/// store construction.
+#[derive(Debug, Clone, Copy)]
+pub struct ByteQuota {
+    limit: u64,
+}
+
+impl ByteQuota {
+    /// Create a new [`ByteQuota`] with the given byte `limit`.
+    #[must_use]
+    pub fn new(limit: u64) -> Self {
+        Self { limit }
+    }
+
+    /// The configured byte limit.
+    #[must_use]
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+}
+
+/// The store's incrementally-maintained size accounting: the total size in bytes, and the length
+/// of every key that makes it up. Held behind the [`AsyncIcechunkStore::sizes`] lock so that a
+/// quota check and the write it gates can never race with a concurrent writer, and so that
+/// per-key lengths are served from memory instead of a `getsize` round trip on every write.
+#[derive(Debug, Default)]
+struct SizeState {
+    total: u64,
+    key_lengths: std::collections::HashMap<StoreKey, u64>,
+}
+
+impl SizeState {
+    fn key_length(&self, key: &StoreKey) -> u64 {
+        self.key_lengths.get(key).copied().unwrap_or(0)
+    }
+
+    /// Record that `key` now has length `new_len` (0 if erased), updating both the per-key map
+    /// and the running total.
+    fn record(&mut self, key: &StoreKey, new_len: u64) {
+        self.total -= self.key_length(key);
+        self.total += new_len;
+        if new_len == 0 {
+            self.key_lengths.remove(key);
+        } else {
+            self.key_lengths.insert(key.clone(), new_len);
+        }
+    }
+}
+
 /// An asynchronous store backed by an [`icechunk::session::Session`].
 pub struct AsyncIcechunkStore {
     icechunk_session: Arc<RwLock<icechunk::session::Session>>,
+    /// Lazily initialised from the session's base snapshot on first use, and invalidated by
+    /// [`AsyncIcechunkStore::commit_rebasing`] whenever it pulls in another writer's changes. The
+    /// write lock also serialises [`AsyncWritableStorageTraits::set`]/
+    /// [`AsyncWritableStorageTraits::set_partial_values`]/[`AsyncWritableStorageTraits::erase`]/
+    /// [`AsyncWritableStorageTraits::erase_prefix`] against each other, so a quota check and the
+    /// write it gates are atomic, and overlapping writes to the same key cannot race and silently
+    /// lose an update.
+    sizes: Arc<RwLock<Option<SizeState>>>,
+    quota: Option<ByteQuota>,
 }
 
 impl From<Arc<RwLock<icechunk::session::Session>>> for AsyncIcechunkStore {
     fn from(icechunk_session: Arc<RwLock<icechunk::session::Session>>) -> Self {
-        Self { icechunk_session }
+        Self {
+            icechunk_session,
+            sizes: Arc::new(RwLock::new(None)),
+            quota: None,
+        }
     }
 }
 
@@ -102,6 +466,18 @@ impl AsyncIcechunkStore {
     pub fn new(icechunk_session: icechunk::session::Session) -> Self {
         Self {
             icechunk_session: Arc::new(RwLock::new(icechunk_session)),
+            sizes: Arc::new(RwLock::new(None)),
+            quota: None,
+        }
+    }
+
+    /// Create a new [`AsyncIcechunkStore`] that rejects writes which would push its total size
+    /// past `quota`.
+    #[must_use]
+    pub fn new_with_quota(icechunk_session: icechunk::session::Session, quota: ByteQuota) -> Self {
+        Self {
+            quota: Some(quota),
+            ..Self::new(icechunk_session)
         }
     }
 
@@ -111,6 +487,30 @@ impl AsyncIcechunkStore {
         self.icechunk_session.clone()
     }
 
+    /// Return the store's size-accounting lock, initialising it from the session's base snapshot
+    /// the first time it is needed.
+    async fn sizes(&self) -> Result<tokio::sync::RwLockWriteGuard<'_, Option<SizeState>>, StorageError> {
+        let mut sizes = self.sizes.write().await;
+        if sizes.is_none() {
+            let keys = self.list_prefix(&StorePrefix::root()).await?;
+            let mut state = SizeState::default();
+            for key in keys {
+                let len = handle_result(self.store().await.getsize(key.as_str()).await)?;
+                state.record(&key, len);
+            }
+            *sizes = Some(state);
+        }
+        Ok(sizes)
+    }
+
+    /// Invalidate the cached size/quota accounting so it is recomputed from the store on next
+    /// use. Must be called after anything that changes the session's committed data without
+    /// going through this store's own `set`/`set_partial_values`/`erase*` methods, such as
+    /// [`AsyncIcechunkStore::commit_rebasing`] pulling in another writer's changes.
+    async fn invalidate_sizes(&self) {
+        *self.sizes.write().await = None;
+    }
+
     // TODO: Wait for async closures
     // // /// Run a method on the underlying session.
     // pub async fn with_session<F, T>(&self, f: F) -> icechunk::session::SessionResult<T>
@@ -174,37 +574,136 @@ impl AsyncReadableStorageTraits for AsyncIcechunkStore {
     }
 }
 
+/// A dedicated error for a [`AsyncIcechunkStore`] write that would exceed its configured
+/// [`ByteQuota`].
+#[derive(Debug, thiserror::Error)]
+#[error("write of {written} bytes would bring the store to {projected} bytes, exceeding the {limit} byte quota")]
+pub struct ByteQuotaExceededError {
+    written: u64,
+    projected: u64,
+    limit: u64,
+}
+
 #[async_trait::async_trait]
 impl AsyncWritableStorageTraits for AsyncIcechunkStore {
     async fn set(&self, key: &StoreKey, value: AsyncBytes) -> Result<(), StorageError> {
+        let new_len = u64::try_from(value.len()).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        // Hold the size-accounting lock for the whole operation: the quota check and the write it
+        // gates must be atomic (no concurrent writer may also pass the check before either lands),
+        // and the previous length comes from memory rather than a `getsize` round trip.
+        let mut sizes_guard = self.sizes().await?;
+        let sizes = sizes_guard.as_mut().expect("initialised above");
+        let previous_len = sizes.key_length(key);
+        if let Some(quota) = self.quota {
+            let projected = sizes.total - previous_len + new_len;
+            if projected > quota.limit() {
+                return Err(StorageError::Other(
+                    ByteQuotaExceededError {
+                        written: new_len,
+                        projected,
+                        limit: quota.limit(),
+                    }
+                    .to_string(),
+                ));
+            }
+        }
+
         handle_result(self.store().await.set(key.as_str(), value).await)?;
+        sizes.record(key, new_len);
         Ok(())
     }
 
     async fn set_partial_values(
         &self,
-        _key_start_values: &[StoreKeyOffsetValue],
+        key_start_values: &[StoreKeyOffsetValue],
     ) -> Result<(), StorageError> {
-        if self
-            .store()
-            .await
-            .supports_partial_writes()
-            .map_err(handle_err)?
-        {
-            // FIXME: Upstream: icechunk::Store does not support partial writes
-            Err(StorageError::Unsupported(
-                "the store does not support partial writes".to_string(),
-            ))
-        } else {
-            Err(StorageError::Unsupported(
-                "the store does not support partial writes".to_string(),
-            ))
+        // Group the offset/value entries by key, preserving the request order of each group so
+        // that overlapping writes to the same key are applied in the order they were given.
+        let mut keys = vec![];
+        let mut entries_by_key: std::collections::HashMap<&StoreKey, Vec<&StoreKeyOffsetValue>> =
+            std::collections::HashMap::new();
+        for key_start_value in key_start_values {
+            entries_by_key
+                .entry(key_start_value.key())
+                .or_insert_with(|| {
+                    keys.push(key_start_value.key());
+                    Vec::new()
+                })
+                .push(key_start_value);
+        }
+
+        // Hold the size-accounting lock for the whole multi-key read-modify-write. This makes it
+        // genuinely atomic with respect to other `set`/`set_partial_values`/`erase*` calls on this
+        // store (unlike `self.store()`, which is just a cheap, repeatable wrapper with no locking
+        // of its own), and serves each key's previous length from memory instead of a `getsize`
+        // round trip.
+        let store = self.store().await;
+        let mut sizes_guard = self.sizes().await?;
+        let sizes = sizes_guard.as_mut().expect("initialised above");
+
+        // First pass: build every key's new buffer and total the batch's net effect on size,
+        // without writing anything yet. A later key in the same batch exceeding the quota must
+        // not leave earlier keys in the batch already durably written and counted.
+        let mut buffers = Vec::with_capacity(keys.len());
+        let mut projected = sizes.total;
+        for key in keys {
+            let previous_len = sizes.key_length(key);
+            let mut buffer = if previous_len == 0 {
+                Vec::new()
+            } else {
+                handle_result_notfound(
+                    store.get(key.as_str(), &icechunk::format::ByteRange::ALL).await,
+                )?
+                .map(|bytes| bytes.to_vec())
+                .unwrap_or_default()
+            };
+
+            for entry in &entries_by_key[key] {
+                let offset = usize::try_from(entry.offset())
+                    .map_err(|err| StorageError::Other(err.to_string()))?;
+                let value = entry.value();
+                let end = offset + value.len();
+                if buffer.len() < end {
+                    buffer.resize(end, 0);
+                }
+                buffer[offset..end].copy_from_slice(value);
+            }
+
+            let new_len = buffer.len() as u64;
+            projected = projected - previous_len + new_len;
+            buffers.push((key, new_len, buffer));
         }
+
+        if let Some(quota) = self.quota {
+            if projected > quota.limit() {
+                return Err(StorageError::Other(
+                    ByteQuotaExceededError {
+                        written: projected.saturating_sub(sizes.total),
+                        projected,
+                        limit: quota.limit(),
+                    }
+                    .to_string(),
+                ));
+            }
+        }
+
+        // Second pass: the whole batch fits the quota, so it is safe to actually write it out.
+        for (key, new_len, buffer) in buffers {
+            handle_result(store.set(key.as_str(), buffer.into()).await)?;
+            sizes.record(key, new_len);
+        }
+        Ok(())
     }
 
     async fn erase(&self, key: &StoreKey) -> Result<(), StorageError> {
         if self.store().await.supports_deletes().map_err(handle_err)? {
-            handle_result_notfound(self.store().await.delete(key.as_str()).await)?;
+            let mut sizes_guard = self.sizes().await?;
+            let sizes = sizes_guard.as_mut().expect("initialised above");
+            let erased = handle_result_notfound(self.store().await.delete(key.as_str()).await)?;
+            if erased.is_some() {
+                sizes.record(key, 0);
+            }
             Ok(())
         } else {
             Err(StorageError::Unsupported(
@@ -224,8 +723,11 @@ impl AsyncWritableStorageTraits for AsyncIcechunkStore {
                 .try_collect::<Vec<_>>() // TODO: do not collect, use try_for_each
                 .await
                 .map_err(handle_err)?;
+            let mut sizes_guard = self.sizes().await?;
+            let sizes = sizes_guard.as_mut().expect("initialised above");
             for key in keys {
                 self.store().await.delete(&key).await.map_err(handle_err)?;
+                sizes.record(&StoreKey::new(&key)?, 0);
             }
             Ok(())
         } else {
@@ -292,6 +794,23 @@ impl AsyncListableStorageTraits for AsyncIcechunkStore {
     }
 
     async fn size_prefix(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
+        if prefix == &StorePrefix::root() {
+            // The root prefix is covered by the incremental size state, so this is O(1).
+            return Ok(self.sizes().await?.as_ref().expect("initialised above").total);
+        }
+        self.size_prefix_uncached(prefix).await
+    }
+
+    async fn size(&self) -> Result<u64, StorageError> {
+        self.size_prefix(&StorePrefix::root()).await
+    }
+}
+
+impl AsyncIcechunkStore {
+    /// List every key under `prefix` and sum their sizes with an individual `getsize` request
+    /// per key. Used only to answer [`AsyncListableStorageTraits::size_prefix`] for a non-root
+    /// prefix, which the incremental size state does not track.
+    async fn size_prefix_uncached(&self, prefix: &StorePrefix) -> Result<u64, StorageError> {
         let keys = self.list_prefix(prefix).await?;
         let mut futures: FuturesUnordered<_> = keys
             .into_iter()
@@ -306,9 +825,100 @@ impl AsyncListableStorageTraits for AsyncIcechunkStore {
         }
         Ok(sum)
     }
+}
 
-    async fn size(&self) -> Result<u64, StorageError> {
-        self.size_prefix(&StorePrefix::root()).await
+fn handle_repo_err(err: icechunk::repository::RepositoryError) -> StorageError {
+    StorageError::Other(err.to_string())
+}
+
+/// A thin companion to [`AsyncIcechunkStore`] that manages branches and tags directly through an
+/// [`icechunk::Repository`], so a zarrs application can implement named releases and experiment
+/// branches of a dataset without reaching into icechunk's types itself.
+pub struct IcechunkRepository {
+    repository: icechunk::Repository,
+}
+
+impl IcechunkRepository {
+    /// Wrap an existing [`icechunk::Repository`] handle.
+    #[must_use]
+    pub fn new(repository: icechunk::Repository) -> Self {
+        Self { repository }
+    }
+
+    /// Return the inner [`icechunk::Repository`].
+    #[must_use]
+    pub fn repository(&self) -> &icechunk::Repository {
+        &self.repository
+    }
+
+    /// Create a new branch named `branch` pointing at `snapshot`.
+    pub async fn create_branch(
+        &self,
+        branch: &str,
+        snapshot: &icechunk::format::snapshot::SnapshotId,
+    ) -> Result<(), StorageError> {
+        self.repository
+            .create_branch(branch, snapshot)
+            .await
+            .map_err(handle_repo_err)
+    }
+
+    /// Delete the branch named `branch`.
+    pub async fn delete_branch(&self, branch: &str) -> Result<(), StorageError> {
+        self.repository
+            .delete_branch(branch)
+            .await
+            .map_err(handle_repo_err)
+    }
+
+    /// Create a new immutable tag named `tag` pointing at `snapshot`.
+    pub async fn create_tag(
+        &self,
+        tag: &str,
+        snapshot: &icechunk::format::snapshot::SnapshotId,
+    ) -> Result<(), StorageError> {
+        self.repository
+            .create_tag(tag, snapshot)
+            .await
+            .map_err(handle_repo_err)
+    }
+
+    /// Delete the tag named `tag`.
+    pub async fn delete_tag(&self, tag: &str) -> Result<(), StorageError> {
+        self.repository
+            .delete_tag(tag)
+            .await
+            .map_err(handle_repo_err)
+    }
+
+    /// Resolve `version` (a branch name, tag name, or snapshot id) to its current snapshot.
+    pub async fn resolve(
+        &self,
+        version: &icechunk::repository::VersionInfo,
+    ) -> Result<icechunk::format::snapshot::SnapshotId, StorageError> {
+        self.repository
+            .resolve_version(version)
+            .await
+            .map_err(handle_repo_err)
+    }
+
+    /// List the commit history of `branch`'s tip, most recent first.
+    pub async fn ancestry(
+        &self,
+        branch: &str,
+    ) -> Result<Vec<icechunk::format::snapshot::SnapshotInfo>, StorageError> {
+        let tip = self
+            .repository
+            .lookup_branch(branch)
+            .await
+            .map_err(handle_repo_err)?;
+        self.repository
+            .ancestry(&icechunk::repository::VersionInfo::SnapshotId(tip))
+            .await
+            .map_err(handle_repo_err)?
+            .try_collect::<Vec<_>>()
+            .await
+            .map_err(handle_repo_err)
     }
 }
 
@@ -394,4 +1004,275 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn diff_nets_add_modify_delete_and_add_then_delete() -> Result<(), Box<dyn Error>> {
+        let storage = icechunk::new_in_memory_storage()?;
+        let config = RepositoryConfig::default();
+        let repo = Repository::create(Some(config), storage, HashMap::new()).await?;
+
+        let untouched = StoreKey::new("array/c/0/0").unwrap();
+        let modified = StoreKey::new("array/c/0/1").unwrap();
+        let added = StoreKey::new("array/c/1/0").unwrap();
+        let added_then_deleted = StoreKey::new("array/c/1/1").unwrap();
+        let deleted = StoreKey::new("array/c/2/0").unwrap();
+        let deleted_then_recreated = StoreKey::new("array/c/2/1").unwrap();
+
+        // The base snapshot: everything that exists before the diff range starts.
+        let base_store = AsyncIcechunkStore::new(repo.writable_session("main").await?);
+        base_store.set(&untouched, vec![0u8].into()).await?;
+        base_store.set(&modified, vec![0u8].into()).await?;
+        base_store.set(&deleted, vec![0u8].into()).await?;
+        base_store
+            .set(&deleted_then_recreated, vec![0u8].into())
+            .await?;
+        let snapshot_from = base_store
+            .session()
+            .write()
+            .await
+            .commit("base", None)
+            .await?;
+
+        // First commit within the range.
+        let store = AsyncIcechunkStore::new(repo.writable_session("main").await?);
+        store.set(&added, vec![1u8].into()).await?;
+        store.set(&added_then_deleted, vec![1u8].into()).await?;
+        store.set(&modified, vec![1u8].into()).await?;
+        store.erase(&deleted).await?;
+        store.erase(&deleted_then_recreated).await?;
+        store.session().write().await.commit("first", None).await?;
+
+        // Second commit within the range: cancels out `added_then_deleted`, and shows that
+        // `deleted_then_recreated` (which existed in the base snapshot) nets to a modification
+        // rather than an addition.
+        let store = AsyncIcechunkStore::new(repo.writable_session("main").await?);
+        store.erase(&added_then_deleted).await?;
+        store
+            .set(&deleted_then_recreated, vec![2u8].into())
+            .await?;
+        let snapshot_to = store
+            .session()
+            .write()
+            .await
+            .commit("second", None)
+            .await?;
+
+        let diff = store
+            .diff(
+                &VersionInfo::SnapshotId(snapshot_from),
+                &VersionInfo::SnapshotId(snapshot_to),
+            )
+            .await?;
+
+        assert_eq!(
+            diff.added.into_iter().collect::<std::collections::HashSet<_>>(),
+            std::collections::HashSet::from([added])
+        );
+        assert_eq!(
+            diff.modified
+                .into_iter()
+                .collect::<std::collections::HashSet<_>>(),
+            std::collections::HashSet::from([modified, deleted_then_recreated])
+        );
+        assert_eq!(
+            diff.deleted
+                .into_iter()
+                .collect::<std::collections::HashSet<_>>(),
+            std::collections::HashSet::from([deleted])
+        );
+
+        Ok(())
+    }
+
+    /// A [`ConflictResolver`] that records every conflict it is asked about and always keeps the
+    /// branch tip's change.
+    struct KeepTheirsResolver {
+        seen: std::sync::Mutex<Vec<StoreKey>>,
+    }
+
+    impl ConflictResolver for KeepTheirsResolver {
+        fn resolve(&self, conflict: &CommitConflict) -> ConflictDecision {
+            self.seen.lock().unwrap().push(conflict.key.clone());
+            ConflictDecision::KeepTheirs
+        }
+    }
+
+    #[tokio::test]
+    async fn commit_rebasing_detects_chunk_conflict() -> Result<(), Box<dyn Error>> {
+        let storage = icechunk::new_in_memory_storage()?;
+        let config = RepositoryConfig::default();
+        let repo = Repository::create(Some(config), storage, HashMap::new()).await?;
+
+        let chunk_key = StoreKey::new("array/c/0/0").unwrap();
+
+        let base_store = AsyncIcechunkStore::new(repo.writable_session("main").await?);
+        base_store.set(&chunk_key, vec![0u8].into()).await?;
+        base_store
+            .session()
+            .write()
+            .await
+            .commit("base", None)
+            .await?;
+
+        // Two sessions both based on the snapshot above, writing to the same chunk.
+        let store_a = AsyncIcechunkStore::new(repo.writable_session("main").await?);
+        let store_b = AsyncIcechunkStore::new(repo.writable_session("main").await?);
+
+        store_a.set(&chunk_key, vec![1u8].into()).await?;
+        store_a
+            .session()
+            .write()
+            .await
+            .commit("a", None)
+            .await?;
+
+        store_b.set(&chunk_key, vec![2u8].into()).await?;
+
+        let resolver = KeepTheirsResolver {
+            seen: std::sync::Mutex::new(vec![]),
+        };
+        store_b.commit_rebasing("b", &resolver, 3).await?;
+
+        // The resolver must have been consulted about the conflicting chunk key, and its
+        // KeepTheirs decision must have been honoured: the branch tip keeps `store_a`'s value.
+        assert_eq!(resolver.seen.into_inner().unwrap(), vec![chunk_key.clone()]);
+
+        let session = repo
+            .readonly_session(&VersionInfo::BranchTipRef("main".to_string()))
+            .await?;
+        let tip_store = AsyncIcechunkStore::new(session);
+        assert_eq!(tip_store.get(&chunk_key).await?, Some(vec![1u8].into()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn set_partial_values_concurrent_writes_not_lost() -> Result<(), Box<dyn Error>> {
+        let storage = icechunk::new_in_memory_storage()?;
+        let config = RepositoryConfig::default();
+        let repo = Repository::create(Some(config), storage, HashMap::new()).await?;
+        let store = Arc::new(AsyncIcechunkStore::new(repo.writable_session("main").await?));
+
+        let key = StoreKey::new("array/c/0/0").unwrap();
+
+        // Two overlapping calls concurrently extend the same, initially-empty key with disjoint
+        // byte ranges. Without a lock spanning each call's whole read-modify-write, one call's
+        // stale local buffer (read before the other's write lands) clobbers the other's range.
+        let store_a = store.clone();
+        let key_a = key.clone();
+        let write_a = async move {
+            store_a
+                .set_partial_values(&[StoreKeyOffsetValue::new(key_a, 0, vec![b'A'; 4].into())])
+                .await
+        };
+        let store_b = store.clone();
+        let key_b = key.clone();
+        let write_b = async move {
+            store_b
+                .set_partial_values(&[StoreKeyOffsetValue::new(key_b, 4, vec![b'B'; 4].into())])
+                .await
+        };
+        let (result_a, result_b) = tokio::join!(write_a, write_b);
+        result_a?;
+        result_b?;
+
+        let value = store.get(&key).await?.expect("key should have been written");
+        assert_eq!(value.len(), 8);
+        assert_eq!(&value[0..4], [b'A'; 4]);
+        assert_eq!(&value[4..8], [b'B'; 4]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn quota_rejects_oversized_write_and_size_tracks_overwrite_and_erase(
+    ) -> Result<(), Box<dyn Error>> {
+        let storage = icechunk::new_in_memory_storage()?;
+        let config = RepositoryConfig::default();
+        let repo = Repository::create(Some(config), storage, HashMap::new()).await?;
+        let store = AsyncIcechunkStore::new_with_quota(
+            repo.writable_session("main").await?,
+            ByteQuota::new(4),
+        );
+
+        let key = StoreKey::new("array/c/0/0").unwrap();
+
+        // A write within the quota succeeds, and `size` reflects it exactly.
+        store.set(&key, vec![0u8; 4].into()).await?;
+        assert_eq!(store.size().await?, 4);
+
+        // A write that would push the total past the quota is rejected, and the rejection must
+        // not have perturbed the accounted size.
+        let oversized = StoreKey::new("array/c/0/1").unwrap();
+        assert!(store.set(&oversized, vec![0u8; 4].into()).await.is_err());
+        assert_eq!(store.size().await?, 4);
+
+        // Overwriting the same key with a smaller value shrinks the total rather than adding to
+        // it (the old length must be subtracted, not just the new length added).
+        store.set(&key, vec![0u8; 2].into()).await?;
+        assert_eq!(store.size().await?, 2);
+
+        // Erasing the key removes its contribution entirely.
+        store.erase(&key).await?;
+        assert_eq!(store.size().await?, 0);
+
+        // With the key gone, a write up to the full quota now succeeds.
+        store.set(&oversized, vec![0u8; 4].into()).await?;
+        assert_eq!(store.size().await?, 4);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn icechunk_repository_branch_and_tag_smoke_test() -> Result<(), Box<dyn Error>> {
+        let storage = icechunk::new_in_memory_storage()?;
+        let config = RepositoryConfig::default();
+        let repo = Repository::create(Some(config), storage, HashMap::new()).await?;
+
+        let store = AsyncIcechunkStore::new(repo.writable_session("main").await?);
+        store
+            .set(
+                &StoreKey::new("zarr.json").unwrap(),
+                r#"{"zarr_format":3,"node_type":"group"}"#.into(),
+            )
+            .await?;
+        let snapshot = store
+            .session()
+            .write()
+            .await
+            .commit("base", None)
+            .await?;
+
+        let icechunk_repo = IcechunkRepository::new(repo);
+
+        icechunk_repo.create_branch("experiment", &snapshot).await?;
+        assert_eq!(
+            icechunk_repo
+                .resolve(&VersionInfo::BranchTipRef("experiment".to_string()))
+                .await?,
+            snapshot
+        );
+
+        icechunk_repo.create_tag("v1", &snapshot).await?;
+        assert_eq!(
+            icechunk_repo
+                .resolve(&VersionInfo::TagRef("v1".to_string()))
+                .await?,
+            snapshot
+        );
+
+        icechunk_repo.delete_branch("experiment").await?;
+        assert!(icechunk_repo
+            .resolve(&VersionInfo::BranchTipRef("experiment".to_string()))
+            .await
+            .is_err());
+
+        icechunk_repo.delete_tag("v1").await?;
+        assert!(icechunk_repo
+            .resolve(&VersionInfo::TagRef("v1".to_string()))
+            .await
+            .is_err());
+
+        Ok(())
+    }
 }